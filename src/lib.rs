@@ -80,6 +80,117 @@ mod tests {
         assert_eq!(hb_fsm.state(), State::Dead);
     }
 
+    #[test]
+    fn phi_detects_before_timeout() {
+        let mut hb_fsm = HbFsm::new(0);
+        let T = 1000u64;
+        let W = 0u64;
+
+        // a regular 100-tick cadence, enough gaps to trust phi
+        for now in (0u64..=400u64).step_by(100) {
+            hb_fsm.step(now, Hb::Seen, T, W);
+            assert_eq!(hb_fsm.state(), State::Alive);
+        }
+
+        // a gap far outside the observed cadence, but still inside T;
+        // phi alone must raise suspicion before the fixed timeout would
+        hb_fsm.step(900, Hb::NotSeen, T, W);
+        assert!(hb_fsm.phi() >= 8.0);
+        assert_eq!(hb_fsm.state(), State::Suspect);
+    }
+
+    #[test]
+    fn phi_requires_minimum_samples() {
+        let mut hb_fsm = HbFsm::new(0);
+        let T = 10_000u64;
+        let W = 0u64;
+
+        // only two gaps observed; too few to trust phi
+        hb_fsm.step(0, Hb::Seen, T, W);
+        hb_fsm.step(100, Hb::Seen, T, W);
+        hb_fsm.step(200, Hb::Seen, T, W);
+
+        // age is wildly outside the two observed gaps, but with fewer than
+        // the minimum sample count, the detector must fall back to the
+        // fixed-T path and report no suspicion at all
+        hb_fsm.step(500, Hb::NotSeen, T, W);
+        assert_eq!(hb_fsm.phi(), 0.0);
+        assert_eq!(hb_fsm.state(), State::Alive);
+    }
+
+    #[test]
+    fn phi_sigma_floor_prevents_blowup() {
+        let mut hb_fsm = HbFsm::new(0);
+        let T = 1000u64;
+        let W = 0u64;
+
+        // perfectly regular cadence; raw stddev of the gaps is 0
+        for now in (0u64..=400u64).step_by(100) {
+            hb_fsm.step(now, Hb::Seen, T, W);
+        }
+
+        // right on the observed mean gap; without a sigma floor this would
+        // divide by zero and blow phi up to infinity
+        hb_fsm.step(500, Hb::NotSeen, T, W);
+        assert!(hb_fsm.phi().is_finite());
+        assert!(hb_fsm.phi() < 8.0);
+        assert_eq!(hb_fsm.state(), State::Alive);
+    }
+
+    #[test]
+    fn set_phi_threshold_changes_suspicion_sensitivity() {
+        let T = 1000u64;
+        let W = 0u64;
+
+        // identical observed cadence and the same moderately anomalous
+        // gap fed to two otherwise-identical FSMs; only the configured
+        // phi_threshold differs
+        let mut default_threshold = HbFsm::new(0);
+        let mut lowered_threshold = HbFsm::new(0);
+        lowered_threshold.set_phi_threshold(5.0);
+
+        for now in (0u64..=400u64).step_by(100) {
+            default_threshold.step(now, Hb::Seen, T, W);
+            lowered_threshold.step(now, Hb::Seen, T, W);
+        }
+
+        default_threshold.step(550, Hb::NotSeen, T, W);
+        lowered_threshold.step(550, Hb::NotSeen, T, W);
+
+        // the same phi clears the lowered threshold but not the default
+        assert_eq!(default_threshold.phi(), lowered_threshold.phi());
+        assert_eq!(default_threshold.state(), State::Alive);
+        assert_eq!(lowered_threshold.state(), State::Suspect);
+    }
+
+    #[test]
+    fn suspicion_contract() {
+        let mut hb_fsm = HbFsm::new(0);
+        let T = 1000u64;
+        let W = 500u64;
+
+        hb_fsm.step(0, Hb::Seen, T, W);
+        assert_eq!(hb_fsm.state(), State::Alive);
+
+        // past T but still within the grace window W; must be Suspect,
+        // never Dead
+        hb_fsm.step(T + 1, Hb::NotSeen, T, W);
+        assert_eq!(hb_fsm.state(), State::Suspect);
+
+        hb_fsm.step(T + W, Hb::NotSeen, T, W);
+        assert_eq!(hb_fsm.state(), State::Suspect);
+
+        // a heartbeat while Suspect restores Alive; death was never
+        // falsely reported
+        hb_fsm.step(T + W + 1, Hb::Seen, T, W);
+        assert_eq!(hb_fsm.state(), State::Alive);
+
+        // past T + W with no heartbeat; now confirmed Dead
+        let last = hb_fsm.last_hb();
+        hb_fsm.step(last + T + W + 1, Hb::NotSeen, T, W);
+        assert_eq!(hb_fsm.state(), State::Dead);
+    }
+
     #[test]
     fn invariants_hold() {
         let mut hb_fsm = HbFsm::new(0);
@@ -121,6 +232,25 @@ mod tests {
         assert_eq!(hb_fsm.faulted(), true);
     }
 
+    #[test]
+    fn clock_corruption_on_seen_cant_poison_phi() {
+        let mut hb_fsm = HbFsm::new(1000);
+        let T = 1000u64;
+        let W = 0u64;
+
+        hb_fsm.step(1000, Hb::Seen, T, W);
+        assert_eq!(hb_fsm.state(), State::Alive);
+
+        // a clock jump backwards arriving as a heartbeat must not be
+        // silently admitted into the phi gap window as a huge wrapped
+        // "gap" (age = 500 - 1000, wraps to a value near u64::MAX);
+        // it has to trip the same fault_time/Dead path as NotSeen does
+        hb_fsm.step(500, Hb::Seen, T, W);
+
+        assert_eq!(hb_fsm.state(), State::Dead);
+        assert_eq!(hb_fsm.faulted(), true);
+    }
+
     use std::sync::{Arc, Barrier};
 
     #[test]
@@ -206,16 +336,177 @@ mod tests {
         }
     }
 
+    #[test]
+    fn monitor_evicts_lru_on_count_cap() {
+        let mut monitor: Monitor<&str> = Monitor::new(2, 1_000, 1000, 0);
+
+        monitor.register("a", 0);
+        monitor.register("b", 0);
+
+        // cap is 2 entries; registering a third must evict the
+        // least-recently-updated one ("a") first
+        monitor.register("c", 0);
+
+        assert_eq!(monitor.len(), 2);
+        assert_eq!(monitor.remove(&"a"), None);
+        assert!(monitor.remove(&"b").is_some());
+        assert!(monitor.remove(&"c").is_some());
+    }
+
+    #[test]
+    fn monitor_heartbeat_refreshes_lru_order() {
+        let mut monitor: Monitor<&str> = Monitor::new(2, 1_000, 1000, 0);
+
+        monitor.register("a", 0);
+        monitor.register("b", 0);
+
+        // touching "a" makes "b" the least-recently-updated entry
+        monitor.heartbeat(&"a", 1);
+
+        monitor.register("c", 2);
+
+        assert_eq!(monitor.remove(&"b"), None);
+        assert!(monitor.remove(&"a").is_some());
+        assert!(monitor.remove(&"c").is_some());
+    }
+
+    #[test]
+    fn monitor_evicts_lru_on_weight_cap() {
+        // each entry costs a fixed weight (the phi gap-window size, 32);
+        // a cap of 64 fits exactly two
+        let mut monitor: Monitor<&str> = Monitor::new(100, 64, 1000, 0);
+
+        monitor.register("a", 0);
+        monitor.register("b", 0);
+
+        // no room left under the weight cap; "a" (LRU) must be evicted
+        monitor.register("c", 0);
+
+        assert_eq!(monitor.len(), 2);
+        assert_eq!(monitor.remove(&"a"), None);
+        assert!(monitor.remove(&"b").is_some());
+        assert!(monitor.remove(&"c").is_some());
+    }
+
+    #[test]
+    fn monitor_remove_frees_weight_for_new_entries() {
+        let mut monitor: Monitor<&str> = Monitor::new(100, 64, 1000, 0);
+
+        monitor.register("a", 0);
+        monitor.register("b", 0);
+
+        // freeing "a"'s weight must let a new entry in without evicting "b"
+        assert!(monitor.remove(&"a").is_some());
+        monitor.register("c", 0);
+
+        assert_eq!(monitor.len(), 2);
+        assert!(monitor.remove(&"b").is_some());
+        assert!(monitor.remove(&"c").is_some());
+    }
+
+    #[test]
+    fn monitor_poll_reports_every_tracked_state() {
+        let mut monitor: Monitor<&str> = Monitor::new(10, 1_000, 1000, 0);
+
+        monitor.register("a", 0);
+        monitor.heartbeat(&"a", 0);
+        monitor.register("b", 0);
+        monitor.heartbeat(&"b", 0);
+
+        let mut states = monitor.poll(1001);
+        states.sort_by_key(|(k, _)| *k);
+
+        assert_eq!(states, vec![("a", State::Dead), ("b", State::Dead)]);
+    }
+
+    #[test]
+    fn monitor_advance_returns_only_due_entries() {
+        let mut monitor: Monitor<&str> = Monitor::new(10, 1_000, 1000, 0);
+
+        monitor.register("a", 0);
+        monitor.heartbeat(&"a", 0);
+        monitor.register("b", 100);
+        monitor.heartbeat(&"b", 100);
+
+        // neither deadline (1000, 1100) has passed yet
+        assert_eq!(monitor.advance(500), vec![]);
+
+        // only "a"'s deadline (1000) has passed; "b" must not be touched
+        assert_eq!(monitor.advance(1001), vec![("a", State::Dead)]);
+
+        // now "b"'s deadline (1100) has passed
+        assert_eq!(monitor.advance(1101), vec![("b", State::Dead)]);
+    }
+
+    #[test]
+    fn monitor_next_deadline_tracks_minimum_across_reschedules() {
+        let mut monitor: Monitor<&str> = Monitor::new(10, 1_000, 1000, 500);
+
+        monitor.register("a", 0);
+        monitor.heartbeat(&"a", 0);
+        monitor.register("b", 0);
+        monitor.heartbeat(&"b", 200);
+
+        // "a"'s plain deadline (last_hb + T = 1000) is sooner than "b"'s
+        // (last_hb + T = 1200)
+        assert_eq!(monitor.next_deadline(), Some(1000));
+
+        // "a" is now Suspect, so its next deadline moves out to
+        // last_hb + T + W = 1500; "b" (1200) becomes the new minimum
+        assert_eq!(monitor.advance(1001), vec![("a", State::Suspect)]);
+        assert_eq!(monitor.next_deadline(), Some(1200));
+
+        // "b" follows the same pattern once it is suspected too
+        assert_eq!(monitor.advance(1201), vec![("b", State::Suspect)]);
+        assert_eq!(monitor.next_deadline(), Some(1500));
+    }
+
+    #[test]
+    fn monitor_heartbeat_discards_stale_heap_entry() {
+        let mut monitor: Monitor<&str> = Monitor::new(10, 1_000, 1000, 0);
+
+        monitor.register("a", 0);
+        monitor.heartbeat(&"a", 0);
+
+        // this leaves the original last_hb + T = 1000 heap entry stale
+        monitor.heartbeat(&"a", 500);
+
+        // the stale 1000 entry must be skipped, not treated as due
+        assert_eq!(monitor.advance(1001), vec![]);
+        assert_eq!(monitor.next_deadline(), Some(1500));
+    }
+
+    #[test]
+    fn monitor_surfaces_never_heartbeated_entry_once() {
+        let mut monitor: Monitor<&str> = Monitor::new(10, 1_000, 1000, 0);
+
+        // registered, but never heartbeated
+        monitor.register("a", 0);
+
+        // before t_init + T (1000), nothing is due
+        assert_eq!(monitor.next_deadline(), Some(1000));
+        assert_eq!(monitor.advance(999), vec![]);
+
+        // once t_init + T passes, it surfaces from advance exactly once;
+        // it was never Alive, so it reports State::Unknown, not Dead
+        assert_eq!(monitor.advance(1001), vec![("a", State::Unknown)]);
+
+        // the one-shot deadline isn't rescheduled; it never fires again
+        assert_eq!(monitor.next_deadline(), None);
+        assert_eq!(monitor.advance(10_000), vec![]);
+    }
+
     // helper functions
     //
     fn verify_invariants(m: &HbFsm) {
         // check for valid state
         assert!(m.state() == State::Unknown ||
                 m.state() == State::Alive   ||
+                m.state() == State::Suspect ||
                 m.state() == State::Dead);
 
-        // State::Alive requires evidence
-        if m.state() == State::Alive {
+        // State::Alive and State::Suspect both require evidence
+        if m.state() == State::Alive || m.state() == State::Suspect {
             assert_eq!(m.has_evidence(), true);
         }
 
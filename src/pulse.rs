@@ -2,6 +2,7 @@
 pub enum State {
     Unknown,
     Alive,
+    Suspect,
     Dead,
 }
 
@@ -21,8 +22,18 @@ impl Hb {
     }
 }
 
+use std::collections::VecDeque;
 use std::sync::atomic::{AtomicBool, Ordering};
 
+// phi-accrual tuning: how many inter-arrival gaps we keep for the running
+// mean/stddev, how many we require before trusting phi over the fixed-T
+// fallback, the default suspicion threshold, and the sigma floor (in ticks)
+// that keeps phi finite when a process's cadence is perfectly regular.
+const PHI_GAP_WINDOW: usize = 32;
+const PHI_MIN_SAMPLES: usize = 3;
+const PHI_SIGMA_FLOOR: f64 = 10.0;
+const PHI_DEFAULT_THRESHOLD: f64 = 8.0;
+
 pub struct HbFsm {
     state:          State,
     t_init:         u64,
@@ -31,6 +42,9 @@ pub struct HbFsm {
     fault_time:     bool,
     fault_reentry:  bool,
     in_step:        AtomicBool,
+    hb_gaps:        VecDeque<u64>,
+    phi:            f64,
+    phi_threshold:  f64,
 }
 
 impl HbFsm {
@@ -43,6 +57,9 @@ impl HbFsm {
             fault_time:     false,
             fault_reentry:  false,
             in_step:        AtomicBool::new(false),
+            hb_gaps:        VecDeque::new(),
+            phi:            0.0,
+            phi_threshold:  PHI_DEFAULT_THRESHOLD,
         }
     }
 
@@ -54,6 +71,8 @@ impl HbFsm {
         self.fault_time = false;
         self.fault_reentry = false;
         self.in_step.store(false, Ordering::Relaxed);
+        self.hb_gaps.clear();
+        self.phi = 0.0;
     }
 
     pub fn step(&mut self, now: u64, hb: Hb, T: u64, W: u64) {
@@ -70,12 +89,30 @@ impl HbFsm {
         }
 
         if hb == Hb::Seen {
+            if self.have_hb {
+                let gap = Self::age_u64(&now, &self.last_hb);
+                if !Self::age_valid(&gap) {
+                    if self.faulted() {
+                        return;
+                    }
+                    self.fault_time = true;
+                    self.state = State::Dead;
+                    self.in_step.store(false, Ordering::Release);
+                    return;
+                }
+                self.push_gap(gap);
+            }
             self.last_hb = now;
             self.have_hb = true;
         }
 
         if !self.have_hb {
             let a_init: u64 = Self::age_u64(&now, &self.t_init);
+            // a concurrent thread may have detected reentry and faulted us
+            // while we were off the CAS; never let our own write clobber it
+            if self.faulted() {
+                return;
+            }
             if !Self::age_valid(&a_init) {
                 self.fault_time = true;
                 self.state = State::Dead;
@@ -88,25 +125,43 @@ impl HbFsm {
 
         let a_hb: u64 = Self::age_u64(&now, &self.last_hb);
         if !Self::age_valid(&a_hb) {
+            if self.faulted() {
+                return;
+            }
             self.fault_time = true;
             self.state = State::Dead;
             self.in_step.store(false, Ordering::Release);
             return;
         }
 
-        if a_hb > T {
-            self.state = State::Dead;
-        } else {
-            self.state = State::Alive;
+        self.phi = self.compute_phi(a_hb);
+
+        let phi_suspected = self.hb_gaps.len() >= PHI_MIN_SAMPLES && self.phi >= self.phi_threshold;
+        let suspected = a_hb > T || phi_suspected;
+        let confirmed_dead = a_hb > T + W;
+
+        // re-check immediately before the write: the phi computation above
+        // gives a concurrent thread a wider window to detect reentry and
+        // fault us first, and that fault must win
+        if self.faulted() {
+            return;
         }
 
+        self.state = if confirmed_dead {
+            State::Dead
+        } else if suspected {
+            State::Suspect
+        } else {
+            State::Alive
+        };
+
         self.in_step.store(false, Ordering::Release);
     }
 
     #[inline]
     pub fn state(&self) -> State {
         self.state
-    } 
+    }
 
     #[inline]
     pub fn has_evidence(&self) -> bool {
@@ -118,6 +173,11 @@ impl HbFsm {
         self.last_hb
     }
 
+    #[inline]
+    pub fn t_init(&self) -> u64 {
+        self.t_init
+    }
+
     #[inline]
     pub fn faulted(&self) -> bool {
         self.fault_time || self.fault_reentry
@@ -128,6 +188,17 @@ impl HbFsm {
         self.in_step.load(Ordering::Relaxed)
     }
 
+    // current phi (suspicion) value computed on the last `step`; 0.0 until
+    // enough heartbeat gaps have accumulated to trust the statistic
+    #[inline]
+    pub fn phi(&self) -> f64 {
+        self.phi
+    }
+
+    pub fn set_phi_threshold(&mut self, threshold: f64) {
+        self.phi_threshold = threshold;
+    }
+
     // private helpers for age calculation and validation
     #[inline]
     fn age_u64(now: &u64, then: &u64) -> u64 {
@@ -138,4 +209,319 @@ impl HbFsm {
     fn age_valid(age: &u64) -> bool {
         *age < (1u64 << 63)
     }
+
+    // private helpers for the phi-accrual suspicion calculation
+    fn push_gap(&mut self, gap: u64) {
+        if self.hb_gaps.len() == PHI_GAP_WINDOW {
+            self.hb_gaps.pop_front();
+        }
+        self.hb_gaps.push_back(gap);
+    }
+
+    // running mean and stddev of the tracked inter-arrival gaps, with sigma
+    // clamped to a small floor so a perfectly regular cadence can't drive
+    // phi to infinity
+    fn gap_stats(&self) -> (f64, f64) {
+        let n = self.hb_gaps.len() as f64;
+        let mean = self.hb_gaps.iter().map(|&g| g as f64).sum::<f64>() / n;
+        let variance = self.hb_gaps.iter()
+            .map(|&g| { let d = g as f64 - mean; d * d })
+            .sum::<f64>() / n;
+        (mean, variance.sqrt().max(PHI_SIGMA_FLOOR))
+    }
+
+    // phi = -log10(P(gap > elapsed)) under a normal(mu, sigma) model of the
+    // observed inter-arrival gaps; 0.0 until PHI_MIN_SAMPLES gaps are in
+    fn compute_phi(&self, elapsed: u64) -> f64 {
+        if self.hb_gaps.len() < PHI_MIN_SAMPLES {
+            return 0.0;
+        }
+
+        let (mean, sigma) = self.gap_stats();
+        let x = (elapsed as f64 - mean) / sigma;
+        let p_later = (1.0 - Self::std_normal_cdf(x)).max(1e-16);
+        -p_later.log10()
+    }
+
+    fn std_normal_cdf(x: f64) -> f64 {
+        0.5 * (1.0 + Self::erf(x / std::f64::consts::SQRT_2))
+    }
+
+    // Abramowitz & Stegun 7.1.26 approximation of the error function
+    fn erf(x: f64) -> f64 {
+        let sign = if x < 0.0 { -1.0 } else { 1.0 };
+        let x = x.abs();
+
+        let a1 =  0.254829592;
+        let a2 = -0.284496736;
+        let a3 =  1.421413741;
+        let a4 = -1.453152027;
+        let a5 =  1.061405429;
+        let p  =  0.3275911;
+
+        let t = 1.0 / (1.0 + p * x);
+        let y = 1.0 - (((((a5 * t + a4) * t) + a3) * t + a2) * t + a1) * t * (-x * x).exp();
+
+        sign * y
+    }
+}
+
+use linked_hash_map::LinkedHashMap;
+use std::collections::BinaryHeap;
+use std::hash::Hash;
+use std::cmp::Reverse;
+
+// every tracked HbFsm carries the same bounded gap window, so it costs the
+// same fixed "weight" against the monitor's memory cap
+const ENTRY_WEIGHT: usize = PHI_GAP_WINDOW;
+
+struct MonitorEntry {
+    fsm:           HbFsm,
+    weight:        usize,
+    // the expiry this entry most recently scheduled into the deadline
+    // heap; a heap entry is current only while it matches this value
+    next_deadline: Option<u64>,
+}
+
+// a single entry in the deadline min-heap, ordered by `expiry` alone so
+// that `K` need not be `Ord`
+struct Deadline<K> {
+    expiry: u64,
+    key:    K,
+}
+
+impl<K> PartialEq for Deadline<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.expiry == other.expiry
+    }
+}
+
+impl<K> Eq for Deadline<K> {}
+
+impl<K> PartialOrd for Deadline<K> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K> Ord for Deadline<K> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.expiry.cmp(&other.expiry)
+    }
+}
+
+// a bounded, keyed collection of HbFsm instances for supervising many
+// processes at once. Insertion/access order is tracked via a linked hash
+// map so that once either the entry-count or total-weight cap is exceeded,
+// the least-recently-updated entries are evicted first, like a weighted
+// LRU cache.
+//
+// A deadline-ordered min-heap tracks each entry's next expiry (t_init + T
+// before any heartbeat, last_hb + T while Alive, or last_hb + T + W once
+// Suspect) so `advance` only does work proportional to the entries that
+// actually came due, instead of sweeping every tracked process on every
+// tick. A process that never sends a first heartbeat still surfaces from
+// `advance` once its t_init + T deadline passes.
+pub struct Monitor<K: Hash + Eq + Clone> {
+    entries:     LinkedHashMap<K, MonitorEntry>,
+    deadlines:   BinaryHeap<Reverse<Deadline<K>>>,
+    max_entries: usize,
+    max_weight:  usize,
+    weight:      usize,
+    t:           u64,
+    w:           u64,
+}
+
+impl<K: Hash + Eq + Clone> Monitor<K> {
+    pub fn new(max_entries: usize, max_weight: usize, t: u64, w: u64) -> Self {
+        Self {
+            entries:     LinkedHashMap::new(),
+            deadlines:   BinaryHeap::new(),
+            max_entries,
+            max_weight,
+            weight: 0,
+            t,
+            w,
+        }
+    }
+
+    // register a new process under `key`, evicting least-recently-updated
+    // entries until both caps are satisfied
+    pub fn register(&mut self, key: K, now: u64) {
+        if self.entries.contains_key(&key) {
+            return;
+        }
+
+        self.evict_to_fit(ENTRY_WEIGHT);
+
+        let scheduling_key = key.clone();
+
+        self.entries.insert(key, MonitorEntry {
+            fsm:           HbFsm::new(now),
+            weight:        ENTRY_WEIGHT,
+            next_deadline: None,
+        });
+        self.weight += ENTRY_WEIGHT;
+
+        // schedule the one-shot t_init + T deadline so a process that
+        // never sends a first heartbeat still surfaces from `advance`
+        self.schedule_initial(&scheduling_key);
+    }
+
+    // record a heartbeat for `key`, marking it most-recently-used
+    pub fn heartbeat(&mut self, key: &K, now: u64) {
+        let present = if let Some(entry) = self.entries.get_refresh(key) {
+            entry.fsm.step(now, Hb::Seen, self.t, self.w);
+            true
+        } else {
+            false
+        };
+
+        if present {
+            self.reschedule(key);
+        }
+    }
+
+    // sweep every tracked process for its current liveness state, without
+    // disturbing LRU order
+    pub fn poll(&mut self, now: u64) -> Vec<(K, State)> {
+        let keys: Vec<K> = self.entries.iter().map(|(k, _)| k.clone()).collect();
+
+        keys.into_iter()
+            .map(|key| {
+                if let Some(entry) = self.entries.get_mut(&key) {
+                    entry.fsm.step(now, Hb::NotSeen, self.t, self.w);
+                }
+                self.reschedule(&key);
+                let state = self.entries.get(&key).map(|e| e.fsm.state()).unwrap();
+                (key, state)
+            })
+            .collect()
+    }
+
+    // stop tracking `key`, returning its last known state if it was present
+    pub fn remove(&mut self, key: &K) -> Option<State> {
+        let entry = self.entries.remove(key)?;
+        self.weight -= entry.weight;
+        Some(entry.fsm.state())
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // the soonest expiry among all tracked processes, so an event loop can
+    // sleep until it instead of busy-polling
+    pub fn next_deadline(&mut self) -> Option<u64> {
+        self.drop_stale_top();
+        self.deadlines.peek().map(|Reverse(d)| d.expiry)
+    }
+
+    // process exactly the entries whose deadline has passed `now`,
+    // returning their (possibly now-Suspect or now-Dead) state; work is
+    // proportional to the number of entries actually due, not to the
+    // total number tracked.
+    //
+    // A deadline of `last_hb + T` is the last instant HbFsm still reports
+    // Alive (a_hb == T), so an entry is only due once `now` has strictly
+    // passed it — using `<=` here would re-fire (and reschedule to the
+    // same expiry) forever whenever `now` lands exactly on a deadline.
+    pub fn advance(&mut self, now: u64) -> Vec<(K, State)> {
+        let mut due = Vec::new();
+
+        loop {
+            self.drop_stale_top();
+
+            let is_due = matches!(self.deadlines.peek(), Some(Reverse(d)) if d.expiry < now);
+            if !is_due {
+                break;
+            }
+
+            let Reverse(deadline) = self.deadlines.pop().unwrap();
+
+            if let Some(entry) = self.entries.get_mut(&deadline.key) {
+                entry.fsm.step(now, Hb::NotSeen, self.t, self.w);
+                let state = entry.fsm.state();
+                self.reschedule(&deadline.key);
+                due.push((deadline.key, state));
+            }
+        }
+
+        due
+    }
+
+    // evict LRU entries until room for `incoming` exists under both caps
+    fn evict_to_fit(&mut self, incoming: usize) {
+        while self.entries.len() >= self.max_entries
+            || self.weight + incoming > self.max_weight
+        {
+            match self.entries.pop_front() {
+                Some((_, evicted)) => self.weight -= evicted.weight,
+                None => break,
+            }
+        }
+    }
+
+    // schedule the one-shot deadline for a just-registered, still-Unknown
+    // entry (t_init + T); `reschedule` treats State::Unknown as
+    // unschedulable, so this is the only deadline such an entry ever gets
+    fn schedule_initial(&mut self, key: &K) {
+        let expiry = match self.entries.get(key) {
+            Some(entry) => entry.fsm.t_init() + self.t,
+            None => return,
+        };
+
+        if let Some(entry) = self.entries.get_mut(key) {
+            entry.next_deadline = Some(expiry);
+        }
+        self.deadlines.push(Reverse(Deadline { expiry, key: key.clone() }));
+    }
+
+    // compute `key`'s next expiry from its FSM's current state and push a
+    // fresh heap entry; stale entries left behind by earlier pushes are
+    // discarded lazily on pop, by checking against `next_deadline`. A
+    // still-Unknown entry (never heartbeated) is left unscheduled here so
+    // that its one-shot `schedule_initial` deadline doesn't refire forever.
+    fn reschedule(&mut self, key: &K) {
+        let expiry = match self.entries.get(key) {
+            Some(entry) => match entry.fsm.state() {
+                State::Alive   => Some(entry.fsm.last_hb() + self.t),
+                State::Suspect => Some(entry.fsm.last_hb() + self.t + self.w),
+                State::Unknown | State::Dead => None,
+            },
+            None => return,
+        };
+
+        if let Some(entry) = self.entries.get_mut(key) {
+            entry.next_deadline = expiry;
+        }
+
+        if let Some(expiry) = expiry {
+            self.deadlines.push(Reverse(Deadline { expiry, key: key.clone() }));
+        }
+    }
+
+    // a heap entry is current only if it matches the expiry its key's
+    // entry most recently scheduled; anything else is a stale re-push
+    fn is_current(&self, deadline: &Deadline<K>) -> bool {
+        matches!(
+            self.entries.get(&deadline.key).map(|e| e.next_deadline),
+            Some(Some(expiry)) if expiry == deadline.expiry
+        )
+    }
+
+    fn drop_stale_top(&mut self) {
+        while let Some(Reverse(top)) = self.deadlines.peek() {
+            if self.is_current(top) {
+                break;
+            }
+            self.deadlines.pop();
+        }
+    }
 }